@@ -32,34 +32,186 @@ fn read_fasta(file_path: &str) -> io::Result<Vec<String>> {
     Ok(sequences)
 }
 
-fn calculate_pwm(sequences: &[String]) -> Vec<HashMap<char, f64>> {
-    if sequences.is_empty() {
-        return Vec::new();
+// Assumes Phred+33 quality encoding.
+fn read_fastq(file_path: &str) -> io::Result<Vec<(String, Vec<f64>)>> {
+    let file = File::open(file_path)?;
+    let reader = io::BufReader::new(file);
+    let mut lines = reader.lines();
+
+    let mut records = Vec::new();
+    while let Some(header) = lines.next() {
+        let header = header?;
+        if !header.starts_with('@') {
+            continue;
+        }
+
+        let seq = lines
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "missing sequence line"))??;
+        let _plus = lines
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "missing '+' line"))??;
+        let qual = lines
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "missing quality line"))??;
+
+        if qual.len() != seq.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "quality line length ({}) does not match sequence length ({})",
+                    qual.len(),
+                    seq.len()
+                ),
+            ));
+        }
+
+        let weights: Vec<f64> = qual
+            .bytes()
+            .map(|b| {
+                let q = (b as i32 - 33) as f64;
+                let p_err = 10f64.powf(-q / 10.0);
+                1.0 - p_err
+            })
+            .collect();
+
+        records.push((seq, weights));
+    }
+
+    Ok(records)
+}
+
+fn count_residues(records: &[(String, Vec<f64>)]) -> (Vec<HashMap<char, f64>>, Vec<f64>) {
+    if records.is_empty() {
+        return (Vec::new(), Vec::new());
     }
 
-    let seq_length = sequences[0].len();
-    let num_sequences = sequences.len();
-    let mut pwm = vec![HashMap::new(); seq_length];
+    let seq_length = records[0].0.len();
+    let mut counts = vec![HashMap::new(); seq_length];
+    let mut total_weight = vec![0.0; seq_length];
 
-    for seq in sequences {
+    for (seq, weights) in records {
         assert_eq!(seq.len(), seq_length, "Sequences must have the same length");
+        assert_eq!(weights.len(), seq_length, "Weights must match sequence length");
 
         for (i, residue) in seq.chars().enumerate() {
-            let count = pwm[i].entry(residue).or_insert(0.0);
-            *count += 1.0;
+            let weight = weights[i];
+            let count = counts[i].entry(residue).or_insert(0.0);
+            *count += weight;
+            total_weight[i] += weight;
         }
     }
 
-    for position in &mut pwm {
+    (counts, total_weight)
+}
+
+fn calculate_pwm(records: &[(String, Vec<f64>)]) -> Vec<HashMap<char, f64>> {
+    let (mut pwm, total_weight) = count_residues(records);
+
+    for (position, &weight) in pwm.iter_mut().zip(total_weight.iter()) {
+        if weight == 0.0 {
+            continue;
+        }
         for aa in AMINO_ACIDS.chars() {
             let count = position.entry(aa).or_insert(0.0);
-            *count /= num_sequences as f64;
+            *count /= weight;
         }
     }
 
     pwm
 }
 
+fn uniform_background() -> HashMap<char, f64> {
+    AMINO_ACIDS
+        .chars()
+        .map(|aa| (aa, 1.0 / AMINO_ACIDS.len() as f64))
+        .collect()
+}
+
+fn read_background(file_path: &str) -> io::Result<HashMap<char, f64>> {
+    let file = File::open(file_path)?;
+    let reader = io::BufReader::new(file);
+    let mut background = HashMap::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut fields = line.split('\t');
+        let aa = fields
+            .next()
+            .and_then(|s| s.chars().next())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing residue in background file"))?;
+        let freq: f64 = fields
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing frequency in background file"))?
+            .trim()
+            .parse()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid frequency in background file"))?;
+
+        background.insert(aa, freq);
+    }
+
+    validate_background(&background)?;
+    Ok(background)
+}
+
+fn validate_background(background: &HashMap<char, f64>) -> io::Result<()> {
+    for aa in AMINO_ACIDS.chars() {
+        match background.get(&aa) {
+            Some(&freq) if freq > 0.0 => {}
+            Some(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("background frequency for '{}' must be greater than 0", aa),
+                ));
+            }
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("background file is missing residue '{}'", aa),
+                ));
+            }
+        }
+    }
+
+    let total: f64 = background.values().sum();
+    if (total - 1.0).abs() > 0.01 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("background frequencies must sum to ~1.0, got {:.4}", total),
+        ));
+    }
+
+    Ok(())
+}
+
+fn log_odds_pwm(
+    records: &[(String, Vec<f64>)],
+    background: &HashMap<char, f64>,
+    pseudocount: f64,
+) -> Vec<HashMap<char, f64>> {
+    let (counts, total_weight) = count_residues(records);
+
+    counts
+        .into_iter()
+        .zip(total_weight.iter())
+        .map(|(position, &n)| {
+            AMINO_ACIDS
+                .chars()
+                .map(|aa| {
+                    let c = *position.get(&aa).unwrap_or(&0.0);
+                    let bg_freq = *background.get(&aa).unwrap_or(&0.0);
+                    let p = (c + pseudocount * bg_freq) / (n + pseudocount);
+                    (aa, (p / bg_freq).log2())
+                })
+                .collect()
+        })
+        .collect()
+}
+
 fn write_pwm_to_tsv(pwm: &[HashMap<char, f64>], output_path: &str) -> io::Result<()> {
     let mut file = File::create(output_path)?;
 
@@ -79,30 +231,628 @@ fn write_pwm_to_tsv(pwm: &[HashMap<char, f64>], output_path: &str) -> io::Result
     Ok(())
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 3 {
-        eprintln!("Usage: {} <fasta_file_path> <output_tsv_path>", args[0]);
+fn information_content(counts: &[HashMap<char, f64>], total_weight: &[f64]) -> Vec<(f64, HashMap<char, f64>)> {
+    let num_residues = AMINO_ACIDS.len() as f64;
+    let max_ic = num_residues.log2();
+
+    counts
+        .iter()
+        .zip(total_weight.iter())
+        .map(|(position, &n)| {
+            if n == 0.0 {
+                let heights: HashMap<char, f64> = AMINO_ACIDS.chars().map(|aa| (aa, 0.0)).collect();
+                return (0.0, heights);
+            }
+
+            let freqs: HashMap<char, f64> = AMINO_ACIDS
+                .chars()
+                .map(|aa| (aa, *position.get(&aa).unwrap_or(&0.0) / n))
+                .collect();
+
+            let entropy: f64 = freqs
+                .values()
+                .filter(|&&p| p > 0.0)
+                .map(|&p| -p * p.log2())
+                .sum();
+
+            let small_sample_correction = (num_residues - 1.0) / (2.0 * std::f64::consts::LN_2 * n);
+            let ic = (max_ic - entropy - small_sample_correction).max(0.0);
+
+            let heights: HashMap<char, f64> = freqs.iter().map(|(&aa, &p)| (aa, p * ic)).collect();
+
+            (ic, heights)
+        })
+        .collect()
+}
+
+fn write_logo_to_tsv(logo: &[(f64, HashMap<char, f64>)], output_path: &str) -> io::Result<()> {
+    let mut file = File::create(output_path)?;
+
+    let header: Vec<String> = AMINO_ACIDS.chars().map(|aa| aa.to_string()).collect();
+    writeln!(file, "Position\tIC\t{}", header.join("\t"))?;
+
+    for (i, (ic, heights)) in logo.iter().enumerate() {
+        let row: Vec<String> = AMINO_ACIDS
+            .chars()
+            .map(|aa| format!("{:.3}", heights.get(&aa).unwrap_or(&0.0)))
+            .collect();
+        writeln!(file, "{}\t{:.3}\t{}", i + 1, ic, row.join("\t"))?;
+    }
+
+    Ok(())
+}
+
+fn read_pwm_from_tsv(file_path: &str) -> io::Result<Vec<HashMap<char, f64>>> {
+    let file = File::open(file_path)?;
+    let reader = io::BufReader::new(file);
+    let mut lines = reader.lines();
+
+    let header = lines
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "empty matrix file"))??;
+    let columns: Vec<char> = header
+        .split('\t')
+        .skip(1)
+        .filter_map(|s| s.chars().next())
+        .collect();
+
+    let mut pwm = Vec::new();
+    for line in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut fields = line.split('\t');
+        fields.next(); // position index, not needed
+
+        let mut position = HashMap::new();
+        for aa in &columns {
+            let score: f64 = fields
+                .next()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "truncated matrix row"))?
+                .trim()
+                .parse()
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid score in matrix file"))?;
+            position.insert(*aa, score);
+        }
+        pwm.push(position);
+    }
+
+    Ok(pwm)
+}
+
+struct ScanHit {
+    start: usize,
+    substring: String,
+    score: f64,
+}
+
+fn score_window(pwm: &[HashMap<char, f64>], residues: &[char], start: usize) -> f64 {
+    pwm.iter()
+        .enumerate()
+        .map(|(k, position)| {
+            *position
+                .get(&residues[start + k])
+                .unwrap_or(&f64::NEG_INFINITY)
+        })
+        .sum()
+}
+
+fn max_possible_score(pwm: &[HashMap<char, f64>]) -> f64 {
+    pwm.iter()
+        .map(|position| {
+            position
+                .values()
+                .cloned()
+                .fold(f64::NEG_INFINITY, f64::max)
+        })
+        .sum()
+}
+
+const DEFAULT_NULL_SAMPLES: usize = 10_000;
+const DEFAULT_NULL_SEED: u64 = 42;
+
+fn lcg_next(state: &mut u64) -> f64 {
+    *state = (*state * 3877 + 29573) % 139968;
+    *state as f64 / 139968.0
+}
+
+fn sample_background_residue(cumulative: &[(char, f64)], state: &mut u64) -> char {
+    let deviate = lcg_next(state);
+    cumulative
+        .iter()
+        .find(|(_, cum)| *cum >= deviate)
+        .map(|(aa, _)| *aa)
+        .unwrap_or_else(|| cumulative.last().unwrap().0)
+}
+
+fn generate_null_scores(
+    pwm: &[HashMap<char, f64>],
+    background: &HashMap<char, f64>,
+    samples: usize,
+    seed: u64,
+) -> Vec<f64> {
+    let motif_len = pwm.len();
+    let mut cumulative = Vec::with_capacity(AMINO_ACIDS.len());
+    let mut running = 0.0;
+    for aa in AMINO_ACIDS.chars() {
+        running += *background.get(&aa).unwrap_or(&0.0);
+        cumulative.push((aa, running));
+    }
+
+    let mut state = seed % 139968;
+    (0..samples)
+        .map(|_| {
+            let residues: Vec<char> = (0..motif_len)
+                .map(|_| sample_background_residue(&cumulative, &mut state))
+                .collect();
+            score_window(pwm, &residues, 0)
+        })
+        .collect()
+}
+
+fn empirical_p_value(null_scores: &[f64], observed: f64) -> f64 {
+    let at_least_as_high = null_scores.iter().filter(|&&s| s >= observed).count();
+    at_least_as_high as f64 / null_scores.len() as f64
+}
+
+fn threshold_for_fpr(null_scores: &[f64], target_fpr: f64) -> f64 {
+    let mut sorted = null_scores.to_vec();
+    sorted.sort_by(|a, b| b.partial_cmp(a).unwrap());
+    let index = ((target_fpr * sorted.len() as f64).ceil() as usize)
+        .saturating_sub(1)
+        .min(sorted.len() - 1);
+    sorted[index]
+}
+
+fn scan_sequence(pwm: &[HashMap<char, f64>], sequence: &str, threshold: f64) -> Vec<ScanHit> {
+    let motif_len = pwm.len();
+    let residues: Vec<char> = sequence.chars().collect();
+    let mut hits = Vec::new();
+
+    if motif_len == 0 || residues.len() < motif_len {
+        return hits;
+    }
+
+    for start in 0..=(residues.len() - motif_len) {
+        let score = score_window(pwm, &residues, start);
+        if score > threshold {
+            hits.push(ScanHit {
+                start,
+                substring: residues[start..start + motif_len].iter().collect(),
+                score,
+            });
+        }
+    }
+
+    hits
+}
+
+fn is_fastq(file_path: &str) -> bool {
+    let lower = file_path.to_lowercase();
+    lower.ends_with(".fastq") || lower.ends_with(".fq")
+}
+
+fn read_records(file_path: &str) -> io::Result<Vec<(String, Vec<f64>)>> {
+    if is_fastq(file_path) {
+        read_fastq(file_path)
+    } else {
+        let sequences = read_fasta(file_path)?;
+        Ok(sequences
+            .into_iter()
+            .map(|seq| {
+                let weights = vec![1.0; seq.len()];
+                (seq, weights)
+            })
+            .collect())
+    }
+}
+
+const DEFAULT_PSEUDOCOUNT: f64 = 0.8;
+
+fn print_usage(program: &str) {
+    eprintln!(
+        "Usage: {0} build <fasta_or_fastq_file_path> <output_tsv_path> [--log-odds] [--background FILE] [--pseudocount ALPHA] [--logo FILE]\n       {0} scan <matrix_tsv_path> <fasta_or_fastq_file_path> <threshold> [--fpr RATE] [--background FILE] [--samples N] [--seed N]",
+        program
+    );
+}
+
+// Parses a flag's value, exiting with a usage error instead of silently falling back
+// to a default when the value is missing or fails to parse.
+fn parse_required_flag<T: std::str::FromStr>(program: &str, flag: &str, raw: Option<&String>) -> T {
+    match raw.and_then(|s| s.parse::<T>().ok()) {
+        Some(value) => value,
+        None => {
+            eprintln!(
+                "Error: {} requires a valid value, got '{}'.",
+                flag,
+                raw.map(String::as_str).unwrap_or("<missing>")
+            );
+            print_usage(program);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_build(program: &str, args: &[String]) {
+    let mut positional = Vec::new();
+    let mut log_odds = false;
+    let mut background_path: Option<String> = None;
+    let mut pseudocount = DEFAULT_PSEUDOCOUNT;
+    let mut logo_path: Option<String> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--log-odds" => log_odds = true,
+            "--background" => {
+                i += 1;
+                background_path = args.get(i).cloned();
+            }
+            "--pseudocount" => {
+                i += 1;
+                pseudocount = parse_required_flag(program, "--pseudocount", args.get(i));
+            }
+            "--logo" => {
+                i += 1;
+                logo_path = args.get(i).cloned();
+            }
+            other => positional.push(other.to_string()),
+        }
+        i += 1;
+    }
+
+    if positional.len() != 2 {
+        print_usage(program);
         std::process::exit(1);
     }
 
-    let fasta_file = &args[1];
-    let output_file = &args[2];
+    let input_file = &positional[0];
+    let output_file = &positional[1];
 
-    if !Path::new(fasta_file).exists() {
-        eprintln!("Error: File '{}' does not exist.", fasta_file);
+    if !Path::new(input_file).exists() {
+        eprintln!("Error: File '{}' does not exist.", input_file);
         std::process::exit(1);
     }
 
-    match read_fasta(fasta_file) {
-        Ok(sequences) => {
-            let pwm = calculate_pwm(&sequences);
-            if let Err(e) = write_pwm_to_tsv(&pwm, output_file) {
-                eprintln!("Error writing to TSV file: {}", e);
-            } else {
-                println!("PWM written to '{}'", output_file);
+    let records = match read_records(input_file) {
+        Ok(records) => records,
+        Err(e) => {
+            eprintln!("Error reading input file: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let pwm = if log_odds {
+        let background = match background_path {
+            Some(path) => match read_background(&path) {
+                Ok(background) => background,
+                Err(e) => {
+                    eprintln!("Error reading background file: {}", e);
+                    std::process::exit(1);
+                }
+            },
+            None => uniform_background(),
+        };
+        log_odds_pwm(&records, &background, pseudocount)
+    } else {
+        calculate_pwm(&records)
+    };
+
+    if let Err(e) = write_pwm_to_tsv(&pwm, output_file) {
+        eprintln!("Error writing to TSV file: {}", e);
+    } else {
+        println!("PWM written to '{}'", output_file);
+    }
+
+    if let Some(logo_file) = logo_path {
+        let (counts, total_weight) = count_residues(&records);
+        let logo = information_content(&counts, &total_weight);
+        if let Err(e) = write_logo_to_tsv(&logo, &logo_file) {
+            eprintln!("Error writing logo TSV file: {}", e);
+        } else {
+            println!("Logo data written to '{}'", logo_file);
+        }
+    }
+}
+
+fn run_scan(program: &str, args: &[String]) {
+    let mut positional = Vec::new();
+    let mut fpr: Option<f64> = None;
+    let mut background_path: Option<String> = None;
+    let mut samples = DEFAULT_NULL_SAMPLES;
+    let mut seed = DEFAULT_NULL_SEED;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--fpr" => {
+                i += 1;
+                fpr = Some(parse_required_flag(program, "--fpr", args.get(i)));
+            }
+            "--background" => {
+                i += 1;
+                background_path = args.get(i).cloned();
             }
+            "--samples" => {
+                i += 1;
+                samples = parse_required_flag(program, "--samples", args.get(i));
+            }
+            "--seed" => {
+                i += 1;
+                seed = parse_required_flag(program, "--seed", args.get(i));
+            }
+            other => positional.push(other.to_string()),
+        }
+        i += 1;
+    }
+
+    if positional.len() != 3 {
+        print_usage(program);
+        std::process::exit(1);
+    }
+
+    let matrix_file = &positional[0];
+    let query_file = &positional[1];
+    let given_threshold: f64 = match positional[2].parse() {
+        Ok(t) => t,
+        Err(_) => {
+            eprintln!("Error: threshold '{}' is not a number.", positional[2]);
+            std::process::exit(1);
+        }
+    };
+
+    let pwm = match read_pwm_from_tsv(matrix_file) {
+        Ok(pwm) => pwm,
+        Err(e) => {
+            eprintln!("Error reading matrix file: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let records = match read_records(query_file) {
+        Ok(records) => records,
+        Err(e) => {
+            eprintln!("Error reading query file: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let background = match background_path {
+        Some(path) => match read_background(&path) {
+            Ok(background) => background,
+            Err(e) => {
+                eprintln!("Error reading background file: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => uniform_background(),
+    };
+
+    let null_scores = generate_null_scores(&pwm, &background, samples, seed);
+    let threshold = match fpr {
+        Some(rate) => threshold_for_fpr(&null_scores, rate),
+        None => given_threshold,
+    };
+
+    let max_score = max_possible_score(&pwm);
+
+    println!("sequence\tstart\tsubstring\tscore\tfraction_of_max\tp_value");
+    for (seq_index, (sequence, _weights)) in records.iter().enumerate() {
+        for hit in scan_sequence(&pwm, sequence, threshold) {
+            println!(
+                "{}\t{}\t{}\t{:.3}\t{:.3}\t{:.5}",
+                seq_index,
+                hit.start,
+                hit.substring,
+                hit.score,
+                hit.score / max_score,
+                empirical_p_value(&null_scores, hit.score)
+            );
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 {
+        print_usage(&args[0]);
+        std::process::exit(1);
+    }
+
+    match args[1].as_str() {
+        "build" => run_build(&args[0], &args[2..]),
+        "scan" => run_scan(&args[0], &args[2..]),
+        other => {
+            eprintln!("Error: unknown subcommand '{}'.", other);
+            print_usage(&args[0]);
+            std::process::exit(1);
         }
-        Err(e) => eprintln!("Error reading FASTA file: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(pairs: &[(&str, &[f64])]) -> Vec<(String, Vec<f64>)> {
+        pairs
+            .iter()
+            .map(|(seq, weights)| (seq.to_string(), weights.to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn read_fastq_rejects_quality_length_mismatch() {
+        let path = std::env::temp_dir().join("pwm_test_read_fastq_rejects_quality_length_mismatch.fastq");
+        std::fs::write(&path, "@r1\nACGTN\n+\n!!!\n").unwrap();
+        let result = read_fastq(path.to_str().unwrap());
+        std::fs::remove_file(&path).ok();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn calculate_pwm_weights_equal_reads_uniformly() {
+        let input = records(&[("AC", &[1.0, 1.0]), ("AC", &[1.0, 1.0])]);
+        let pwm = calculate_pwm(&input);
+
+        assert_eq!(pwm[0][&'A'], 1.0);
+        assert_eq!(pwm[1][&'C'], 1.0);
+    }
+
+    #[test]
+    fn calculate_pwm_weights_low_confidence_residues_proportionally() {
+        let input = records(&[("AA", &[1.0, 0.5]), ("CA", &[1.0, 0.5])]);
+        let pwm = calculate_pwm(&input);
+
+        // Position 0 has full weight: A and C each contribute 1.0 out of 2.0.
+        assert_eq!(pwm[0][&'A'], 0.5);
+        assert_eq!(pwm[0][&'C'], 0.5);
+        // Position 1 is all-A at half weight each, so A's frequency is still 1.0.
+        assert_eq!(pwm[1][&'A'], 1.0);
+    }
+
+    #[test]
+    fn calculate_pwm_skips_zero_weight_position_instead_of_nan() {
+        let input = records(&[("AC", &[1.0, 0.0]), ("AC", &[1.0, 0.0])]);
+        let pwm = calculate_pwm(&input);
+
+        assert_eq!(pwm[0][&'A'], 1.0);
+        assert!(!pwm[1].values().any(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn log_odds_pwm_matches_pseudocount_formula() {
+        let input = records(&[("A", &[1.0]), ("A", &[1.0])]);
+        let background = uniform_background();
+        let pwm = log_odds_pwm(&input, &background, 0.8);
+
+        let bg_a = background[&'A'];
+        let expected = ((2.0 + 0.8 * bg_a) / (2.0 + 0.8) / bg_a).log2();
+        assert!((pwm[0][&'A'] - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn validate_background_rejects_missing_residue() {
+        let mut background = uniform_background();
+        background.remove(&'Y');
+        assert!(validate_background(&background).is_err());
+    }
+
+    #[test]
+    fn validate_background_rejects_zero_frequency() {
+        let mut background = uniform_background();
+        background.insert('Y', 0.0);
+        assert!(validate_background(&background).is_err());
+    }
+
+    #[test]
+    fn validate_background_rejects_non_normalized_sum() {
+        let mut background = uniform_background();
+        background.insert('A', background[&'A'] + 1.0);
+        assert!(validate_background(&background).is_err());
+    }
+
+    #[test]
+    fn validate_background_accepts_uniform_background() {
+        assert!(validate_background(&uniform_background()).is_ok());
+    }
+
+    fn toy_pwm() -> Vec<HashMap<char, f64>> {
+        vec![
+            HashMap::from([('A', 2.0), ('C', -1.0)]),
+            HashMap::from([('A', -1.0), ('C', 2.0)]),
+        ]
+    }
+
+    #[test]
+    fn score_window_sums_matched_positions() {
+        let pwm = toy_pwm();
+        let residues: Vec<char> = "AC".chars().collect();
+        assert_eq!(score_window(&pwm, &residues, 0), 4.0);
+    }
+
+    #[test]
+    fn max_possible_score_takes_best_residue_per_position() {
+        assert_eq!(max_possible_score(&toy_pwm()), 4.0);
+    }
+
+    #[test]
+    fn scan_sequence_reports_only_windows_above_threshold() {
+        let pwm = toy_pwm();
+        let hits = scan_sequence(&pwm, "CCAC", 3.0);
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].start, 2);
+        assert_eq!(hits[0].substring, "AC");
+        assert_eq!(hits[0].score, 4.0);
+    }
+
+    #[test]
+    fn scan_sequence_returns_nothing_when_query_shorter_than_motif() {
+        let pwm = toy_pwm();
+        assert!(scan_sequence(&pwm, "A", 0.0).is_empty());
+    }
+
+    #[test]
+    fn generate_null_scores_is_deterministic_for_a_given_seed() {
+        let pwm = toy_pwm();
+        let background = uniform_background();
+        let a = generate_null_scores(&pwm, &background, 50, 42);
+        let b = generate_null_scores(&pwm, &background, 50, 42);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn generate_null_scores_does_not_overflow_on_large_seeds() {
+        let pwm = toy_pwm();
+        let background = uniform_background();
+        let scores = generate_null_scores(&pwm, &background, 10, u64::MAX);
+        assert_eq!(scores.len(), 10);
+    }
+
+    #[test]
+    fn empirical_p_value_is_fraction_scoring_at_least_as_high() {
+        let null_scores = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(empirical_p_value(&null_scores, 3.0), 0.6);
+    }
+
+    #[test]
+    fn threshold_for_fpr_matches_requested_tail_fraction() {
+        let null_scores = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        // Requesting a 20% FPR over 5 samples should land on the top score.
+        assert_eq!(threshold_for_fpr(&null_scores, 0.2), 5.0);
+    }
+
+    #[test]
+    fn information_content_is_near_max_for_a_fully_conserved_column() {
+        let counts = vec![HashMap::from([('A', 100.0)])];
+        let total_weight = vec![100.0];
+        let logo = information_content(&counts, &total_weight);
+
+        let max_ic = (AMINO_ACIDS.len() as f64).log2();
+        assert!(logo[0].0 > max_ic - 0.2);
+        assert!((logo[0].1[&'A'] - logo[0].0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn information_content_is_clamped_to_zero_for_uniform_column() {
+        let counts = vec![AMINO_ACIDS.chars().map(|aa| (aa, 5.0)).collect()];
+        let total_weight = vec![5.0 * AMINO_ACIDS.len() as f64];
+        let logo = information_content(&counts, &total_weight);
+
+        assert_eq!(logo[0].0, 0.0);
+    }
+
+    #[test]
+    fn information_content_skips_zero_weight_position_instead_of_nan() {
+        let counts = vec![HashMap::new()];
+        let total_weight = vec![0.0];
+        let logo = information_content(&counts, &total_weight);
+
+        assert_eq!(logo[0].0, 0.0);
+        assert!(!logo[0].1.values().any(|v| v.is_nan()));
     }
 }